@@ -397,6 +397,24 @@ Optionally accepts the following attributes:
 * `parallel_safe`: Corresponds to [`PARALLEL SAFE`](https://www.postgresql.org/docs/current/sql-createfunction.html).
 * `parallel_unsafe`: Corresponds to [`PARALLEL UNSAFE`](https://www.postgresql.org/docs/current/sql-createfunction.html).
 * `parallel_restricted`: Corresponds to [`PARALLEL RESTRICTED`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+* `leakproof`: Corresponds to [`LEAKPROOF`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+  + Must be combined with `immutable` or `stable`.
+* `window`: Corresponds to [`WINDOW`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+* `security_definer`: Corresponds to [`SECURITY DEFINER`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+* `security_invoker`: Corresponds to [`SECURITY INVOKER`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+* `set = ("name", "value")`: Corresponds to [`SET name = value`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+  + May be given multiple times, the clauses are emitted in the order they are written.
+  + `value` is emitted as raw SQL, so quote it unless it is a number or identifier: `set = ("work_mem", "'64MB'")`.
+* `support = path::to::function`: Corresponds to [`SUPPORT`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+  + The support function must itself be a `#[pg_extern]` in the same extension.
+* `typmod_in = path::to::Type`: Use the function as the [`TYPMOD_IN`](https://www.postgresql.org/docs/current/sql-createtype.html) of a `#[derive(PostgresType)]`.
+  + The function should accept `Array<&CStr>` and return `i32`.
+* `typmod_out = path::to::Type`: Use the function as the [`TYPMOD_OUT`](https://www.postgresql.org/docs/current/sql-createtype.html) of a `#[derive(PostgresType)]`.
+  + The function should accept `i32` and return `&CStr`.
+* `symbol = "my_symbol"`: Export the function to Postgres under the C symbol `my_symbol` rather than `{function}_wrapper`.
+  + Unlike `name`, which only changes the SQL-visible name, this renames the generated wrapper function.
+* `sql = "CREATE FUNCTION ..."`: Use the given SQL instead of the generated `CREATE FUNCTION` statement.
+  + `@FUNCTION_NAME@` is replaced with the exported symbol, as with a `pgxsql` doc comment block.
 * `no_guard`: Do not use `#[pg_guard]` with the function.
 
 Functions can accept and return any type which `pgx` supports. `pgx` supports many PostgreSQL types by default.
@@ -522,7 +540,10 @@ pub fn pg_extern(attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_extern_attributes(proc_macro2::TokenStream::from(attr.clone()));
 
     let sql_graph_entity_item =
-        sql_entity_graph::PgExtern::new(attr.clone().into(), item.clone().into()).unwrap();
+        match sql_entity_graph::PgExtern::new(attr.clone().into(), item.clone().into()) {
+            Ok(item) => item,
+            Err(e) => return e.to_compile_error().into(),
+        };
 
     let ast = parse_macro_input!(item as syn::Item);
     match ast {
@@ -540,7 +561,7 @@ fn rewrite_item_fn(
     let no_guard = extern_args.contains(&ExternArgs::NoGuard);
 
     let finfo_name = syn::Ident::new(
-        &format!("pg_finfo_{}_wrapper", func.sig.ident),
+        &format!("pg_finfo_{}", sql_graph_entity_submission.wrapper_ident()),
         Span::call_site(),
     );
 
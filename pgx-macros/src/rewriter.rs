@@ -25,7 +25,7 @@ impl PgGuardRewriter {
         let mut stream = proc_macro2::TokenStream::new();
 
         for item in block.items.into_iter() {
-            stream.extend(self.foreign_item(item));
+            stream.extend(self.foreign_item(item, &block.abi));
         }
 
         stream
@@ -70,10 +70,13 @@ impl PgGuardRewriter {
         let rewritten_args = self.rewrite_args(func.clone(), is_raw);
         let rewritten_return_type = self.rewrite_return_type(func.clone());
         let generics = &func.sig.generics;
-        let func_name_wrapper = Ident::new(
-            &format!("{}_wrapper", &func.sig.ident.to_string()),
-            func_span,
-        );
+        let func_name_wrapper = match entity_submission {
+            Some(entity_submission) => entity_submission.wrapper_ident(),
+            None => Ident::new(
+                &format!("{}_wrapper", &func.sig.ident.to_string()),
+                func_span,
+            ),
+        };
 
         let returns_void = rewritten_return_type
             .to_string()
@@ -506,26 +509,38 @@ impl PgGuardRewriter {
         }
     }
 
-    pub fn foreign_item(&self, item: ForeignItem) -> proc_macro2::TokenStream {
+    pub fn foreign_item(&self, item: ForeignItem, abi: &syn::Abi) -> proc_macro2::TokenStream {
         match item {
             ForeignItem::Fn(func) => {
+                // a guard can't forward C varargs, so these are left as plain declarations
                 if func.sig.variadic.is_some() {
-                    return quote! { extern "C" { #func } };
+                    return quote! { #abi { #func } };
                 }
 
-                self.foreign_item_fn(func)
+                self.foreign_item_fn(func, abi)
             }
-            _ => quote! { extern "C" { #item } },
+            // only functions need guarding, so statics and types are passed along as they are
+            _ => quote! { #abi { #item } },
         }
     }
 
-    pub fn foreign_item_fn(&self, func: ForeignItemFn) -> proc_macro2::TokenStream {
+    pub fn foreign_item_fn(&self, func: ForeignItemFn, abi: &syn::Abi) -> proc_macro2::TokenStream {
         let func_name = PgGuardRewriter::build_func_name(&func.sig);
         let arg_list = PgGuardRewriter::rename_arg_list(&func.sig);
         let arg_list_with_types = PgGuardRewriter::rename_arg_list_with_types(&func.sig);
         let return_type = PgGuardRewriter::get_return_type(&func.sig);
 
+        // carry over the doc comments bindgen generated so they're visible on the wrapper
+        let docs = func.attrs.iter().filter(|attr| attr.path.is_ident("doc"));
+
+        // and keep the symbol the declaration actually links against
+        let link_name = func
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("link_name"));
+
         quote! {
+            #(#docs)*
             #[allow(clippy::missing_safety_doc)]
             #[allow(clippy::redundant_closure)]
             #[allow(improper_ctypes_definitions)] /* for i128 */
@@ -537,7 +552,8 @@ impl PgGuardRewriter {
                     panic!("functions under #[pg_guard] cannot be called from threads");
                 };
 
-                extern "C" {
+                #abi {
+                    #(#link_name)*
                     pub fn #func_name( #arg_list_with_types ) #return_type ;
                 }
 
@@ -798,3 +814,72 @@ fn extract_option_type(ty: &Type) -> proc_macro2::TokenStream {
         _ => panic!("No type found inside Option"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PgGuardRewriter;
+    use syn::parse_quote;
+
+    #[test]
+    fn extern_block_keeps_doc_comments() {
+        let block: syn::ItemForeignMod = parse_quote! {
+            extern "C" {
+                #[doc = " POSTGRES initialization and cleanup definitions."]
+                pub fn pg_split_opts(argv: *mut *mut ::std::os::raw::c_char);
+            }
+        };
+
+        let rewritten = PgGuardRewriter::new().extern_block(block).to_string();
+        assert!(rewritten.contains("POSTGRES initialization and cleanup definitions."));
+    }
+
+    #[test]
+    fn extern_block_leaves_variadic_functions_unguarded() {
+        let block: syn::ItemForeignMod = parse_quote! {
+            extern "C" {
+                pub fn errmsg(fmt: *const ::std::os::raw::c_char, ...) -> ::std::os::raw::c_int;
+                pub fn errstart(elevel: ::std::os::raw::c_int, domain: *const ::std::os::raw::c_char) -> bool;
+            }
+        };
+
+        let rewritten = PgGuardRewriter::new().extern_block(block).to_string();
+        let errmsg = rewritten
+            .find("pub fn errmsg")
+            .expect("errmsg should be declared");
+        assert!(rewritten[..errmsg].trim_end().ends_with(r#"extern "C" {"#));
+        assert!(!rewritten.contains("pub unsafe fn errmsg"));
+        assert!(rewritten.contains("pub unsafe fn errstart"));
+    }
+
+    #[test]
+    fn extern_block_only_guards_functions() {
+        let block: syn::ItemForeignMod = parse_quote! {
+            extern "C" {
+                pub static mut work_mem: ::std::os::raw::c_int;
+                pub fn pg_usleep(microsec: ::std::os::raw::c_long);
+            }
+        };
+
+        let rewritten = PgGuardRewriter::new().extern_block(block).to_string();
+        assert!(rewritten.contains(r#"extern "C" { pub static mut work_mem"#));
+        assert!(rewritten.contains("pub unsafe fn pg_usleep"));
+    }
+
+    #[test]
+    fn extern_block_keeps_link_name_and_abi() {
+        let block: syn::ItemForeignMod = parse_quote! {
+            extern "C-unwind" {
+                #[link_name = "\u{1}_pg_strcasecmp"]
+                pub fn pg_strcasecmp(
+                    s1: *const ::std::os::raw::c_char,
+                    s2: *const ::std::os::raw::c_char,
+                ) -> ::std::os::raw::c_int;
+            }
+        };
+
+        let rewritten = PgGuardRewriter::new().extern_block(block).to_string();
+        assert!(rewritten.contains(r#"link_name = "\u{1}_pg_strcasecmp""#));
+        assert!(rewritten.contains(r#"extern "C-unwind""#));
+        assert!(!rewritten.contains(r#"extern "C" "#));
+    }
+}
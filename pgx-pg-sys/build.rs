@@ -8,21 +8,27 @@ use pgx_utils::pg_config::{PgConfig, PgConfigSelector, Pgx};
 use pgx_utils::{exit_with_error, handle_result, prefix_path};
 use quote::quote;
 use rayon::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::process::{Command, Output};
+use std::sync::Mutex;
 use syn::Item;
 
 #[derive(Debug)]
-struct IgnoredMacros(HashSet<String>);
+struct BindgenCallbacks {
+    ignored_macros: HashSet<String>,
+    derives: BTreeMap<String, Vec<String>>,
+}
 
-impl IgnoredMacros {
+impl BindgenCallbacks {
     fn default() -> Self {
-        // these cause duplicate definition problems on linux
-        // see: https://github.com/rust-lang/rust-bindgen/issues/687
-        IgnoredMacros(
-            vec![
+        BindgenCallbacks {
+            // these cause duplicate definition problems on linux
+            // see: https://github.com/rust-lang/rust-bindgen/issues/687
+            ignored_macros: vec![
                 "FP_INFINITE".into(),
                 "FP_NAN".into(),
                 "FP_NORMAL".into(),
@@ -32,18 +38,23 @@ impl IgnoredMacros {
             ]
             .into_iter()
             .collect(),
-        )
+            derives: bindgen_derives(),
+        }
     }
 }
 
-impl bindgen::callbacks::ParseCallbacks for IgnoredMacros {
+impl bindgen::callbacks::ParseCallbacks for BindgenCallbacks {
     fn will_parse_macro(&self, name: &str) -> MacroParsingBehavior {
-        if self.0.contains(name) {
+        if self.ignored_macros.contains(name) {
             bindgen::callbacks::MacroParsingBehavior::Ignore
         } else {
             bindgen::callbacks::MacroParsingBehavior::Default
         }
     }
+
+    fn add_derives(&self, name: &str) -> Vec<String> {
+        self.derives.get(name).cloned().unwrap_or_default()
+    }
 }
 
 fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
@@ -58,7 +69,16 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         }
     }
 
+    println!("cargo:rerun-if-env-changed=PGX_PG_SYS_REWRITE_BINDINGS");
     println!("cargo:rerun-if-env-changed=PGX_PG_SYS_SKIP_BINDING_REWRITE");
+    println!("cargo:rerun-if-env-changed=PGX_BINDINGS_DIFF_REPORT");
+    println!("cargo:rerun-if-env-changed=RUSTFMT");
+    println!("cargo:rerun-if-env-changed=PGX_SHIM_DEBUG");
+    for var in &BINDGEN_BLOCKLIST_VARS {
+        println!("cargo:rerun-if-env-changed={}", var);
+    }
+    println!("cargo:rerun-if-env-changed={}", BINDGEN_EXTRA_HEADER_VAR);
+    println!("cargo:rerun-if-env-changed={}", BINDGEN_DERIVES_VAR);
 
     let manifest_dir = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
     let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
@@ -73,17 +93,62 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     eprintln!("shim_src={}", shim_src.display());
     eprintln!("shim_dst={}", shim_dst.display());
 
-    let pgx = Pgx::from_config()?;
+    println!("cargo:rerun-if-env-changed=PG_CONFIG");
+    for major_version in &[10, 11, 12, 13, 14] {
+        println!("cargo:rerun-if-env-changed=PG{}_PG_CONFIG", major_version);
+    }
+    let pgx = Pgx::from_config().unwrap_or_else(|e| exit_with_error!(e));
 
     build_deps::rerun_if_changed_paths(&Pgx::config_toml()?.display().to_string()).unwrap();
     build_deps::rerun_if_changed_paths("include/*").unwrap();
     build_deps::rerun_if_changed_paths("cshim/pgx-cshim.c").unwrap();
     build_deps::rerun_if_changed_paths("cshim/Makefile").unwrap();
+    if let Some(extra_header) = bindgen_extra_header() {
+        build_deps::rerun_if_changed_paths(&extra_header.display().to_string()).unwrap();
+    }
+
+    // refreshing the committed bindings in `src/` needs every Postgres version, so it's only done
+    // when asked for.  Otherwise we only need the one selected by the `pgXX` feature being built
+    let rewrite_committed_bindings =
+        std::env::var("PGX_PG_SYS_REWRITE_BINDINGS").unwrap_or("false".into()) == "1"
+            && std::env::var("PGX_PG_SYS_SKIP_BINDING_REWRITE").unwrap_or("false".into()) != "1";
+    let feature_label = std::env::vars().find_map(|(key, _)| {
+        key.strip_prefix("CARGO_FEATURE_PG")
+            .map(|version| format!("pg{}", version))
+    });
+    // comparing bindings across versions needs all of them too
+    let diff_report = std::env::var("PGX_BINDINGS_DIFF_REPORT").unwrap_or("false".into()) == "1";
+    let pg_configs = match &feature_label {
+        Some(label) if !rewrite_committed_bindings && !diff_report => match pgx.get(label) {
+            Ok(pg_config) => vec![pg_config],
+            Err(e) => exit_with_error!(
+                "{}.  Add a `{}` entry to {} (`cargo pgx init` does this) or set `{}_PG_CONFIG`",
+                e,
+                label,
+                Pgx::config_toml()?.display(),
+                label.to_uppercase()
+            ),
+        },
+        _ => pgx
+            .iter(PgConfigSelector::All)
+            .map(|v| v.expect("invalid pg_config"))
+            .collect::<Vec<_>>(),
+    };
+    for pg_config in &pg_configs {
+        if let Err(e) = pg_config.validate() {
+            match pg_config.env_var() {
+                Some(var) => exit_with_error!("invalid pg_config in ${}: {}", var, e),
+                None => exit_with_error!(
+                    "invalid pg_config in {}: {}",
+                    Pgx::config_toml()?.display(),
+                    e
+                ),
+            }
+        }
+    }
 
-    let pg_configs = pgx
-        .iter(PgConfigSelector::All)
-        .map(|v| v.expect("invalid pg_config"))
-        .collect::<Vec<_>>();
+    let generated = Mutex::new(Vec::new());
+    let item_names = Mutex::new(Vec::new());
     pg_configs.par_iter().for_each(|pg_config| {
         let major_version = handle_result!(
             pg_config.major_version(),
@@ -93,11 +158,54 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         include_h.push("include");
         include_h.push(format!("pg{}.h", major_version));
 
+        let dest_dirs = if rewrite_committed_bindings {
+            vec![out_dir.clone(), src_dir.clone()]
+        } else {
+            vec![out_dir.clone()]
+        };
+
+        let fingerprint = handle_result!(
+            bindings_fingerprint(&pg_config, &include_h, &dest_dirs),
+            format!(
+                "unable to fingerprint bindings inputs for pg{}",
+                major_version
+            )
+        );
+        let mut fingerprint_file = out_dir.clone();
+        fingerprint_file.push(format!("pg{}.fingerprint", major_version));
+        // the diff report needs freshly generated bindings to compare against
+        if !diff_report
+            && bindings_are_current(&fingerprint_file, &fingerprint, &dest_dirs, major_version)
+        {
+            eprintln!(
+                "Bindings for pg{} are up to date, skipping bindgen",
+                major_version
+            );
+            return;
+        }
+
         let bindgen_output = handle_result!(
             run_bindgen(&pg_config, &include_h),
             format!("bindgen failed for pg{}", major_version)
         );
 
+        if diff_report {
+            let mut committed_file = src_dir.clone();
+            committed_file.push(format!("pg{}.rs", major_version));
+            let committed_names = handle_result!(
+                committed_item_names(&committed_file),
+                format!(
+                    "Unable to read committed bindings file `{}`",
+                    committed_file.display()
+                )
+            );
+            item_names.lock().unwrap().push((
+                major_version,
+                bindgen_item_names(&bindgen_output.items),
+                committed_names,
+            ));
+        }
+
         let rewritten_items = handle_result!(
             rewrite_items(&bindgen_output),
             format!("failed to rewrite items for pg{}", major_version)
@@ -108,13 +216,8 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
             format!("unable to generate oids for pg{}", major_version)
         );
 
-        let dest_dirs =
-            if std::env::var("PGX_PG_SYS_SKIP_BINDING_REWRITE").unwrap_or("false".into()) != "1" {
-                vec![out_dir.clone(), src_dir.clone()]
-            } else {
-                vec![out_dir.clone()]
-            };
-        for dest_dir in dest_dirs {
+        let mut written_files = Vec::new();
+        for dest_dir in &dest_dirs {
             let mut bindings_file = dest_dir.clone();
             bindings_file.push(&format!("pg{}.rs", major_version));
             handle_result!(
@@ -133,6 +236,7 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
                     bindings_file.display()
                 )
             );
+            written_files.push(bindings_file);
 
             let mut oids_file = dest_dir.clone();
             oids_file.push(&format!("pg{}_oids.rs", major_version));
@@ -144,12 +248,39 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
                     oids_file.display()
                 )
             );
+            written_files.push(oids_file);
         }
+
+        generated
+            .lock()
+            .unwrap()
+            .push((fingerprint_file, fingerprint, written_files));
     });
 
-    // compile the cshim for each binding
-    for pg_config in pg_configs {
-        build_shim(&shim_src, &shim_dst, &pg_config)?;
+    // format the generated files only once the parallel section is done, so that concurrent
+    // `rustfmt` processes never race each other
+    let mut generated = generated.into_inner().unwrap();
+    generated.sort();
+    for (fingerprint_file, fingerprint, written_files) in generated {
+        for file in written_files {
+            rust_fmt(&file)?;
+        }
+
+        // only record the fingerprint once the bindings are completely written
+        std::fs::write(&fingerprint_file, &fingerprint)?;
+    }
+
+    if diff_report {
+        report_bindings_diff(item_names.into_inner().unwrap());
+    }
+
+    // compile the cshim for each binding, unless we've been asked to do without it
+    if std::env::var("CARGO_FEATURE_NO_CSHIM").is_ok() {
+        eprintln!("the `no-cshim` feature is enabled, not building the cshim");
+    } else {
+        for pg_config in pg_configs {
+            build_shim(&shim_src, &shim_dst, &pg_config)?;
+        }
     }
 
     Ok(())
@@ -165,8 +296,150 @@ fn write_rs_file(
         #code
     };
 
-    std::fs::write(&file, contents.to_string())?;
-    rust_fmt(&file)
+    std::fs::write(&file, contents.to_string())
+}
+
+/// Hash the inputs that determine the generated bindings for a Postgres version: this build
+/// script itself (which rewrites what bindgen produces), the pgx-pg-sys version, the contents of
+/// our `include/pgXX.h` header, the exact Postgres version and server include directory reported
+/// by `pg_config`, where the bindings are written, and any user-supplied bindgen blocklist, extra
+/// header, or derives
+fn bindings_fingerprint(
+    pg_config: &PgConfig,
+    include_h: &PathBuf,
+    dest_dirs: &[PathBuf],
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut hasher = DefaultHasher::new();
+    include_bytes!("build.rs").hash(&mut hasher);
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    std::fs::read(include_h)?.hash(&mut hasher);
+    // a minor Postgres update can change the headers without moving them
+    pg_config.version_string()?.hash(&mut hasher);
+    pg_config.includedir_server()?.hash(&mut hasher);
+    dest_dirs.hash(&mut hasher);
+    for var in &BINDGEN_BLOCKLIST_VARS {
+        bindgen_blocklist(var).hash(&mut hasher);
+    }
+    bindgen_derives().hash(&mut hasher);
+    if let Some(extra_header) = bindgen_extra_header() {
+        std::fs::read(&extra_header)
+            .map_err(|e| format!("failed to read {}: {}", extra_header.display(), e))?
+            .hash(&mut hasher);
+    }
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+/// Are the bindings we previously generated into every destination directory still valid for
+/// the given fingerprint?
+fn bindings_are_current(
+    fingerprint_file: &PathBuf,
+    fingerprint: &str,
+    dest_dirs: &[PathBuf],
+    major_version: u16,
+) -> bool {
+    match std::fs::read_to_string(fingerprint_file) {
+        Ok(previous) if previous == fingerprint => dest_dirs.iter().all(|dest_dir| {
+            dest_dir.join(format!("pg{}.rs", major_version)).exists()
+                && dest_dir
+                    .join(format!("pg{}_oids.rs", major_version))
+                    .exists()
+        }),
+        _ => false,
+    }
+}
+
+/// Collect the names of all the items bindgen generated, including the members of
+/// `extern "C" { }` blocks
+fn bindgen_item_names(items: &[syn::Item]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for item in items {
+        match item {
+            Item::Const(item) => names.insert(item.ident.to_string()),
+            Item::Enum(item) => names.insert(item.ident.to_string()),
+            Item::Fn(item) => names.insert(item.sig.ident.to_string()),
+            Item::Static(item) => names.insert(item.ident.to_string()),
+            Item::Struct(item) => names.insert(item.ident.to_string()),
+            Item::Type(item) => names.insert(item.ident.to_string()),
+            Item::Union(item) => names.insert(item.ident.to_string()),
+            Item::ForeignMod(block) => {
+                for item in &block.items {
+                    match item {
+                        syn::ForeignItem::Fn(item) => names.insert(item.sig.ident.to_string()),
+                        syn::ForeignItem::Static(item) => names.insert(item.ident.to_string()),
+                        syn::ForeignItem::Type(item) => names.insert(item.ident.to_string()),
+                        _ => false,
+                    };
+                }
+                false
+            }
+            _ => false,
+        };
+    }
+    names
+}
+
+/// Collect the item names from a previously generated bindings file, if there is one.
+///
+/// Our `#[pg_guard]` blocks are plain attributes to `syn`, so the committed file parses just like
+/// fresh bindgen output does
+fn committed_item_names(
+    file: &PathBuf,
+) -> Result<Option<HashSet<String>>, Box<dyn Error + Send + Sync>> {
+    if !file.exists() {
+        return Ok(None);
+    }
+
+    let contents = std::fs::read_to_string(file)?;
+    let parsed = syn::parse_file(&contents)?;
+    Ok(Some(bindgen_item_names(&parsed.items)))
+}
+
+/// Print, per Postgres version, which bindings items are specific to that version, and which
+/// items were added or removed compared to the bindings currently committed in `src/`
+fn report_bindings_diff(mut versions: Vec<(u16, HashSet<String>, Option<HashSet<String>>)>) {
+    fn sorted<'a>(names: impl Iterator<Item = &'a String>) -> Vec<&'a String> {
+        let mut names = names.collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    versions.sort_by_key(|(major_version, _, _)| *major_version);
+    let common = versions
+        .iter()
+        .map(|(_, names, _)| names.clone())
+        .reduce(|common, names| common.intersection(&names).cloned().collect())
+        .unwrap_or_default();
+
+    eprintln!(
+        "[bindings_diff] {} items are common to all versions",
+        common.len()
+    );
+    for (major_version, names, committed) in &versions {
+        let specific = sorted(names.difference(&common));
+        eprintln!(
+            "[bindings_diff] pg{}: {} version-specific items",
+            major_version,
+            specific.len()
+        );
+        for name in specific {
+            eprintln!("[bindings_diff] pg{}:   {}", major_version, name);
+        }
+
+        match committed {
+            Some(committed) => {
+                for name in sorted(names.difference(committed)) {
+                    eprintln!("[bindings_diff] pg{}: + {}", major_version, name);
+                }
+                for name in sorted(committed.difference(names)) {
+                    eprintln!("[bindings_diff] pg{}: - {}", major_version, name);
+                }
+            }
+            None => eprintln!(
+                "[bindings_diff] pg{}: no committed bindings to compare against",
+                major_version
+            ),
+        }
+    }
 }
 
 /// Given a token stream representing a file, apply a series of transformations to munge
@@ -464,6 +737,67 @@ struct StructDescriptor<'a> {
     children: Vec<usize>,
 }
 
+/// Environment variables holding comma-separated lists of additional functions, types, and other
+/// items (such as variables) to leave out of the generated bindings
+const BINDGEN_BLOCKLIST_VARS: [&str; 3] = [
+    "PGX_BINDGEN_BLOCKLIST_FUNCTIONS",
+    "PGX_BINDGEN_BLOCKLIST_TYPES",
+    "PGX_BINDGEN_BLOCKLIST_ITEMS",
+];
+
+fn bindgen_blocklist(var: &str) -> Vec<String> {
+    std::env::var(var)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Names a project-local header whose declarations are generated into the bindings alongside
+/// our own `include/pgXX.h`, such as headers from contrib modules
+const BINDGEN_EXTRA_HEADER_VAR: &str = "PGX_BINDGEN_EXTRA_HEADER";
+
+fn bindgen_extra_header() -> Option<PathBuf> {
+    std::env::var_os(BINDGEN_EXTRA_HEADER_VAR)
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Lists additional traits for specific generated types to derive, on top of the `Debug`,
+/// `Copy`, and `Default` every type gets.  Formatted as `Type:Trait+Trait,OtherType:Trait`
+const BINDGEN_DERIVES_VAR: &str = "PGX_BINDGEN_DERIVES";
+
+fn bindgen_derives() -> BTreeMap<String, Vec<String>> {
+    let mut derives = BTreeMap::new();
+    let var = std::env::var(BINDGEN_DERIVES_VAR).unwrap_or_default();
+    for entry in var
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        match entry.split_once(':') {
+            Some((ty, traits)) => derives
+                .entry(ty.trim().to_string())
+                .or_insert_with(Vec::new)
+                .extend(
+                    traits
+                        .split('+')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(String::from),
+                ),
+            None => exit_with_error!(
+                "{} entry `{}` should look like `Type:Trait+Trait`",
+                BINDGEN_DERIVES_VAR,
+                entry
+            ),
+        }
+    }
+    derives
+}
+
 /// Given a specific postgres version, `run_bindgen` generates bindings for the given
 /// postgres version and returns them as a token stream.
 fn run_bindgen(
@@ -473,10 +807,10 @@ fn run_bindgen(
     let major_version = pg_config.major_version()?;
     eprintln!("Generating bindings for pg{}", major_version);
     let includedir_server = pg_config.includedir_server()?;
-    let bindings = bindgen::Builder::default()
+    let mut builder = bindgen::Builder::default()
         .header(include_h.display().to_string())
         .clang_arg(&format!("-I{}", includedir_server.display()))
-        .parse_callbacks(Box::new(IgnoredMacros::default()))
+        .parse_callbacks(Box::new(BindgenCallbacks::default()))
         .blocklist_function("varsize_any") // pgx converts the VARSIZE_ANY macro, so we don't want to also have this function, which is in heaptuple.c
         .blocklist_function("query_tree_walker")
         .blocklist_function("expression_tree_walker")
@@ -493,14 +827,34 @@ fn run_bindgen(
         .derive_hash(false)
         .derive_ord(false)
         .derive_partialord(false)
-        .layout_tests(false)
-        .generate()
-        .unwrap_or_else(|e| {
-            panic!(
-                "Unable to generate bindings for pg{}: {:?}",
-                major_version, e
-            )
-        });
+        .layout_tests(false);
+
+    if let Some(extra_header) = bindgen_extra_header() {
+        builder = builder.header(extra_header.display().to_string());
+    }
+
+    // these are in addition to the symbols we always block above
+    for function in bindgen_blocklist("PGX_BINDGEN_BLOCKLIST_FUNCTIONS") {
+        builder = builder.blocklist_function(function);
+    }
+    for ty in bindgen_blocklist("PGX_BINDGEN_BLOCKLIST_TYPES") {
+        builder = builder.blocklist_type(ty);
+    }
+    for item in bindgen_blocklist("PGX_BINDGEN_BLOCKLIST_ITEMS") {
+        builder = builder.blocklist_item(item);
+    }
+
+    // bindgen only reports `()` here; clang's own diagnostics were already printed above
+    let bindings = builder.generate().unwrap_or_else(|_| {
+        exit_with_error!(
+            "Unable to generate bindings for pg{} from `{}` with the server headers in `{}`.  Make sure the Postgres server development headers are installed (usually a `postgresql-server-dev-{}` or `postgresql{}-devel` package)",
+            major_version,
+            include_h.display(),
+            includedir_server.display(),
+            major_version,
+            major_version
+        )
+    });
 
     syn::parse_file(bindings.to_string().as_str()).map_err(|e| From::from(e))
 }
@@ -537,45 +891,98 @@ fn build_shim_for_version(
     let path_env = prefix_path(pg_config.parent_path());
     let major_version = pg_config.major_version()?;
 
-    eprintln!("PATH for build_shim={}", path_env);
+    eprintln!("PATH for build_shim={}", path_env.to_string_lossy());
     eprintln!("shim_src={}", shim_src.display());
     eprintln!("shim_dst={}", shim_dst.display());
 
     std::fs::create_dir_all(shim_dst).unwrap();
 
-    if !std::path::Path::new(&format!("{}/Makefile", shim_dst.display())).exists() {
-        std::fs::copy(
-            format!("{}/Makefile", shim_src.display()),
-            format!("{}/Makefile", shim_dst.display()),
-        )
-        .unwrap();
+    for file in &["Makefile", "pgx-cshim.c"] {
+        if !shim_dst.join(file).exists() {
+            std::fs::copy(shim_src.join(file), shim_dst.join(file)).unwrap();
+        }
     }
 
-    if !std::path::Path::new(&format!("{}/pgx-cshim.c", shim_dst.display())).exists() {
-        std::fs::copy(
-            format!("{}/pgx-cshim.c", shim_src.display()),
-            format!("{}/pgx-cshim.c", shim_dst.display()),
-        )
-        .unwrap();
+    let mut make_args = vec![
+        "clean".to_string(),
+        format!("libpgx-cshim-{}.a", major_version),
+    ];
+
+    // when cross-compiling, the shim has to be built for the same target as the Rust code that links it
+    let target = std::env::var("TARGET").unwrap_or_default();
+    let host = std::env::var("HOST").unwrap_or_default();
+    if target != host {
+        if let Some(cc) = target_tool("CC", &target) {
+            make_args.push(format!("CC={}", cc));
+        } else {
+            println!(
+                "cargo:warning=building pgx-cshim for `{}` with the host C compiler.  Set `CC_{}` or `TARGET_CC` to a cross compiler",
+                target,
+                target.replace("-", "_")
+            );
+        }
+        if let Some(ar) = target_tool("AR", &target) {
+            make_args.push(format!("AR={}", ar));
+        }
     }
 
-    let rc = run_command(
-        Command::new("make")
-            .arg("clean")
-            .arg(&format!("libpgx-cshim-{}.a", major_version))
+    let shim_debug = std::env::var("PGX_SHIM_DEBUG").unwrap_or("false".into()) == "1";
+
+    let make = || {
+        let mut make = Command::new("make");
+        make.args(&make_args)
             .env("PG_TARGET_VERSION", format!("{}", major_version))
-            .env("PATH", path_env)
-            .current_dir(shim_dst),
-        &format!("shim for PG v{}", major_version),
-    )?;
+            .env("PATH", &path_env)
+            .current_dir(shim_dst);
+        make
+    };
 
-    if rc.status.code().unwrap() != 0 {
-        panic!("failed to make pgx-cshim for v{}", major_version);
+    if shim_debug {
+        // ask `make` what it's going to run, and make that visible without `cargo build -vv`
+        let dry_run = run_command(
+            make().arg("--dry-run"),
+            &format!("shim for PG v{} (dry run)", major_version),
+        )?;
+        for line in String::from_utf8_lossy(&dry_run.stdout).lines() {
+            println!("cargo:warning=[shim for PG v{}] {}", major_version, line);
+        }
+    }
+
+    let rc = match run_command(&mut make(), &format!("shim for PG v{}", major_version)) {
+        Ok(rc) => rc,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => exit_with_error!(
+            "`make` was not found on the $PATH.  Building pgx-cshim for v{} requires `make` and a C compiler to be installed",
+            major_version
+        ),
+        Err(e) => return Err(e),
+    };
+
+    if !rc.status.success() {
+        exit_with_error!(
+            "failed to make pgx-cshim for v{}:\n{}",
+            major_version,
+            String::from_utf8_lossy(&rc.stderr).trim_end()
+        );
     }
 
     Ok(())
 }
 
+/// Find the cross-compilation `tool` (ie, `CC` or `AR`) for `target`, using the same environment
+/// variables as the `cc` crate
+fn target_tool(tool: &str, target: &str) -> Option<String> {
+    let candidates = [
+        format!("{}_{}", tool, target),
+        format!("{}_{}", tool, target.replace("-", "_")),
+        format!("TARGET_{}", tool),
+    ];
+
+    candidates.iter().find_map(|name| {
+        println!("cargo:rerun-if-env-changed={}", name);
+        std::env::var(name).ok()
+    })
+}
+
 fn run_command(mut command: &mut Command, version: &str) -> Result<Output, std::io::Error> {
     let mut dbg = String::new();
 
@@ -641,20 +1048,32 @@ fn apply_pg_guard(items: &Vec<syn::Item>) -> Result<Vec<syn::Item>, Box<dyn Erro
     Ok(out)
 }
 
+/// The edition the generated bindings are formatted with.  Keep this in sync with the `edition`
+/// in pgx-pg-sys' Cargo.toml
+const RUSTFMT_EDITION: &str = "2018";
+
 fn rust_fmt(path: &PathBuf) -> Result<(), std::io::Error> {
-    run_command(
-        Command::new("rustfmt").arg(path).current_dir("."),
+    // honor `RUSTFMT` the same way `cargo fmt` does, for toolchains where `rustfmt` isn't on the PATH
+    let rustfmt = std::env::var("RUSTFMT").unwrap_or_else(|_| "rustfmt".to_string());
+
+    match run_command(
+        Command::new(&rustfmt)
+            .arg("--edition")
+            .arg(RUSTFMT_EDITION)
+            .arg(path)
+            .current_dir("."),
         "[bindings_diff]",
-    )
-    .map_err(|e| {
-        if e.kind() == std::io::ErrorKind::NotFound {
-            std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Failed to run `rustfmt`, is it installed?",
-            )
-        } else {
-            e
+    ) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            // the bindings are still perfectly usable, they just won't be pretty
+            println!(
+                "cargo:warning=`{}` not found, leaving {} unformatted",
+                rustfmt,
+                path.display()
+            );
+            Ok(())
         }
-    })?;
-    Ok(())
+        Err(e) => Err(e),
+    }
 }
@@ -16,6 +16,11 @@
     any(
         // no features at all will cause problems
         not(any(feature = "pg10", feature = "pg11", feature = "pg12", feature = "pg13", feature = "pg14")),
+        // and neither will more than one, as each version's bindings define the same symbols
+        all(feature = "pg10", any(feature = "pg11", feature = "pg12", feature = "pg13", feature = "pg14")),
+        all(feature = "pg11", any(feature = "pg12", feature = "pg13", feature = "pg14")),
+        all(feature = "pg12", any(feature = "pg13", feature = "pg14")),
+        all(feature = "pg13", feature = "pg14"),
   ))]
 std::compile_error!("exactly one one feature must be provided (pg10, pg11, pg12, pg13, pg14)");
 
@@ -104,6 +109,11 @@ pub use pg14_oids::*;
 pub use all_versions::*;
 
 // and things that are version-specific
+//
+// An item in `internal::pgXX` may also shadow one of the same name from `all_versions` when its
+// definition diverges for that version.  Glob imports of the same name are ambiguous, so such
+// an item must be re-exported by name here (like `IndexBuildHeapScan`), which takes precedence
+// over both the `all_versions::*` and `internal::pgXX::*` globs.
 #[cfg(feature = "pg10")]
 pub use internal::pg10::add_bool_reloption;
 #[cfg(feature = "pg10")]
@@ -179,6 +189,10 @@ impl AsPgCStr for String {
 }
 
 /// item declarations we want to add to all versions
+///
+/// If one of these needs a different definition for a specific Postgres version, add that
+/// definition to the corresponding `internal::pgXX` module and re-export it by name alongside
+/// the other version-specific items above
 mod all_versions {
     use crate as pg_sys;
     use pgx_macros::*;
@@ -190,8 +204,13 @@ mod all_versions {
     pub const InvalidOid: super::Oid = 0;
     pub const InvalidOffsetNumber: super::OffsetNumber = 0;
     pub const FirstOffsetNumber: super::OffsetNumber = 1;
-    pub const MaxOffsetNumber: super::OffsetNumber =
-        (super::BLCKSZ as usize / std::mem::size_of::<super::ItemIdData>()) as super::OffsetNumber;
+    pub const MaxOffsetNumber: super::OffsetNumber = {
+        const MAX: usize = super::BLCKSZ as usize / std::mem::size_of::<super::ItemIdData>();
+        // indexing past the end fails const evaluation, and thus the build, if `MAX` doesn't
+        // fit in an `OffsetNumber` rather than letting the cast below silently truncate it
+        const _: () = [()][(MAX > super::OffsetNumber::MAX as usize) as usize];
+        MAX as super::OffsetNumber
+    };
     pub const InvalidBlockNumber: u32 = 0xFFFF_FFFF as crate::BlockNumber;
     pub const VARHDRSZ: usize = std::mem::size_of::<super::int32>();
     pub const InvalidTransactionId: super::TransactionId = 0 as super::TransactionId;
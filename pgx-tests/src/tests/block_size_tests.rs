@@ -0,0 +1,24 @@
+// Copyright 2020 ZomboDB, LLC <zombodb@gmail.com>. All rights reserved. Use of this source code is
+// governed by the MIT license that can be found in the LICENSE file.
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pgx::pg_schema]
+mod tests {
+    #[allow(unused_imports)]
+    use crate as pgx_tests;
+
+    use pgx::*;
+
+    #[pg_test]
+    fn test_blcksz_matches_server() {
+        let block_size =
+            Spi::get_one::<i32>("SELECT current_setting('block_size')::integer").expect("NULL");
+        assert_eq!(pg_sys::BLCKSZ as i32, block_size);
+    }
+
+    #[pg_test]
+    fn test_max_offset_number_follows_blcksz() {
+        let expected = pg_sys::BLCKSZ as usize / std::mem::size_of::<pg_sys::ItemIdData>();
+        assert_eq!(pg_sys::MaxOffsetNumber as usize, expected);
+    }
+}
@@ -3,6 +3,7 @@
 
 mod anyarray_tests;
 mod array_tests;
+mod block_size_tests;
 mod bytea_tests;
 mod cfg_tests;
 mod datetime_tests;
@@ -20,4 +20,50 @@ mod tests {
         .expect("failed to get SPI result");
         assert!(result)
     }
+
+    #[pg_extern(window)]
+    fn is_window() -> i32 {
+        1
+    }
+
+    #[pg_test]
+    fn test_window() {
+        // `OVER` is rejected unless the function was created with `WINDOW`
+        let result = Spi::get_one::<i32>("SELECT tests.is_window() OVER ()")
+            .expect("failed to get SPI result");
+        assert_eq!(result, 1)
+    }
+
+    #[pg_extern(set = ("work_mem", "'64MB'"), set = ("statement_timeout", "'5s'"))]
+    fn has_set_clauses() {}
+
+    #[pg_test]
+    fn test_set_clauses() {
+        let result = Spi::get_one::<bool>(
+            "SELECT proconfig = ARRAY['work_mem=64MB', 'statement_timeout=5s'] FROM pg_proc WHERE proname = 'has_set_clauses'",
+        )
+        .expect("failed to get SPI result");
+        assert!(result)
+    }
+
+    #[cfg(not(any(feature = "pg10", feature = "pg11")))]
+    #[pg_extern]
+    fn support_fn(_request: Internal) -> Internal {
+        // returning NULL tells the planner this function has nothing to offer
+        Internal::from(None)
+    }
+
+    #[cfg(not(any(feature = "pg10", feature = "pg11")))]
+    #[pg_extern(support = support_fn)]
+    fn has_support_fn() {}
+
+    #[cfg(not(any(feature = "pg10", feature = "pg11")))]
+    #[pg_test]
+    fn test_support() {
+        let result = Spi::get_one::<bool>(
+            "SELECT prosupport = 'tests.support_fn'::regproc FROM pg_proc WHERE proname = 'has_support_fn'",
+        )
+        .expect("failed to get SPI result");
+        assert!(result)
+    }
 }
@@ -60,6 +60,29 @@ pub struct JsonType {
     c: i64,
 }
 
+#[derive(Serialize, Deserialize, PostgresType)]
+pub struct TypmodType {
+    a: i32,
+}
+
+#[pg_extern(immutable, strict, typmod_in = TypmodType)]
+fn typmodtype_typmod_in(modifiers: Array<&CStr>) -> i32 {
+    modifiers
+        .iter_deny_null()
+        .next()
+        .expect("expected one type modifier")
+        .to_str()
+        .unwrap()
+        .parse()
+        .expect("type modifier is not a valid i32")
+}
+
+#[pg_extern(immutable, strict, typmod_out = TypmodType)]
+fn typmodtype_typmod_out(typmod: i32) -> &'static CStr {
+    let modifier = PgMemoryContexts::CurrentMemoryContext.pstrdup(&format!("({})", typmod));
+    unsafe { CStr::from_ptr(modifier) }
+}
+
 #[cfg(any(test, feature = "pg_test"))]
 #[pgx::pg_schema]
 mod tests {
@@ -99,4 +122,20 @@ mod tests {
         assert_eq!(result.b, 2.0);
         assert_eq!(result.c, 3);
     }
+
+    #[pg_test]
+    fn test_typmod_fns() {
+        Spi::run("CREATE TABLE typmod_table (v TypmodType(10))");
+        let typmod = Spi::get_one::<i32>(
+            "SELECT atttypmod FROM pg_attribute WHERE attrelid = 'typmod_table'::regclass AND attname = 'v'",
+        )
+        .expect("SPI returned NULL");
+        assert_eq!(typmod, 10);
+
+        let formatted = Spi::get_one::<String>(
+            "SELECT format_type(atttypid, atttypmod) FROM pg_attribute WHERE attrelid = 'typmod_table'::regclass AND attname = 'v'",
+        )
+        .expect("SPI returned NULL");
+        assert!(formatted.ends_with("typmodtype(10)"));
+    }
 }
@@ -8,6 +8,7 @@ use proc_macro2::TokenTree;
 use quote::{format_ident, quote, ToTokens, TokenStreamExt};
 use serde_json::value::Value as JsonValue;
 use std::collections::HashSet;
+use std::ffi::OsString;
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
 use std::str::FromStr;
@@ -82,15 +83,14 @@ pub fn get_target_dir() -> PathBuf {
     }
 }
 
-pub fn prefix_path<P: Into<PathBuf>>(dir: P) -> String {
+/// Prepend `dir` to the current `$PATH`.  The result is kept as an `OsString` so that directories
+/// with spaces or non-UTF8 names pass through to child processes untouched
+pub fn prefix_path<P: Into<PathBuf>>(dir: P) -> OsString {
     let mut path = std::env::split_paths(&std::env::var_os("PATH").expect("failed to get $PATH"))
         .collect::<Vec<_>>();
 
     path.insert(0, dir.into());
-    std::env::join_paths(path)
-        .expect("failed to join paths")
-        .into_string()
-        .expect("failed to construct path")
+    std::env::join_paths(path).expect("failed to join paths")
 }
 
 pub fn createdb(
@@ -194,9 +194,19 @@ pub enum ExternArgs {
     ParallelSafe,
     ParallelUnsafe,
     ParallelRestricted,
+    Leakproof,
+    Window,
+    SecurityDefiner,
+    SecurityInvoker,
     Error(String),
     Schema(String),
     Name(String),
+    Symbol(String),
+    Sql(String),
+    Set(String, String),
+    Support(String),
+    TypmodIn(String),
+    TypmodOut(String),
     Requires(Vec<PositioningRef>),
 }
 
@@ -211,10 +221,20 @@ impl core::fmt::Display for ExternArgs {
             ExternArgs::ParallelSafe => write!(f, "PARALLEL SAFE"),
             ExternArgs::ParallelUnsafe => write!(f, "PARALLEL UNSAFE"),
             ExternArgs::ParallelRestricted => write!(f, "PARALLEL RESTRICTED"),
+            ExternArgs::Leakproof => write!(f, "LEAKPROOF"),
+            ExternArgs::Window => write!(f, "WINDOW"),
+            ExternArgs::SecurityDefiner => write!(f, "SECURITY DEFINER"),
+            ExternArgs::SecurityInvoker => write!(f, "SECURITY INVOKER"),
             ExternArgs::Error(_) => Ok(()),
             ExternArgs::NoGuard => Ok(()),
             ExternArgs::Schema(_) => Ok(()),
             ExternArgs::Name(_) => Ok(()),
+            ExternArgs::Symbol(_) => Ok(()),
+            ExternArgs::Sql(_) => Ok(()),
+            ExternArgs::Set(_, _) => Ok(()),
+            ExternArgs::Support(_) => Ok(()),
+            ExternArgs::TypmodIn(_) => Ok(()),
+            ExternArgs::TypmodOut(_) => Ok(()),
             ExternArgs::Requires(_) => Ok(()),
         }
     }
@@ -232,6 +252,10 @@ impl ToTokens for ExternArgs {
             ExternArgs::ParallelSafe => tokens.append(format_ident!("ParallelSafe")),
             ExternArgs::ParallelUnsafe => tokens.append(format_ident!("ParallelUnsafe")),
             ExternArgs::ParallelRestricted => tokens.append(format_ident!("ParallelRestricted")),
+            ExternArgs::Leakproof => tokens.append(format_ident!("Leakproof")),
+            ExternArgs::Window => tokens.append(format_ident!("Window")),
+            ExternArgs::SecurityDefiner => tokens.append(format_ident!("SecurityDefiner")),
+            ExternArgs::SecurityInvoker => tokens.append(format_ident!("SecurityInvoker")),
             ExternArgs::Error(_s) => {
                 tokens.append_all(
                     quote! {
@@ -256,6 +280,54 @@ impl ToTokens for ExternArgs {
                     .to_token_stream(),
                 );
             }
+            ExternArgs::Symbol(symbol) => {
+                tokens.append_all(
+                    quote! {
+                        Symbol(String::from(#symbol))
+                    }
+                    .to_token_stream(),
+                );
+            }
+            ExternArgs::Sql(sql) => {
+                tokens.append_all(
+                    quote! {
+                        Sql(String::from(#sql))
+                    }
+                    .to_token_stream(),
+                );
+            }
+            ExternArgs::Set(name, value) => {
+                tokens.append_all(
+                    quote! {
+                        Set(String::from(#name), String::from(#value))
+                    }
+                    .to_token_stream(),
+                );
+            }
+            ExternArgs::Support(path) => {
+                tokens.append_all(
+                    quote! {
+                        Support(String::from(#path))
+                    }
+                    .to_token_stream(),
+                );
+            }
+            ExternArgs::TypmodIn(ty) => {
+                tokens.append_all(
+                    quote! {
+                        TypmodIn(String::from(#ty))
+                    }
+                    .to_token_stream(),
+                );
+            }
+            ExternArgs::TypmodOut(ty) => {
+                tokens.append_all(
+                    quote! {
+                        TypmodOut(String::from(#ty))
+                    }
+                    .to_token_stream(),
+                );
+            }
             ExternArgs::Requires(items) => {
                 tokens.append_all(
                     quote! {
@@ -303,6 +375,10 @@ pub fn parse_extern_attributes(attr: TokenStream) -> HashSet<ExternArgs> {
                     "parallel_safe" => args.insert(ExternArgs::ParallelSafe),
                     "parallel_unsafe" => args.insert(ExternArgs::ParallelUnsafe),
                     "parallel_restricted" => args.insert(ExternArgs::ParallelRestricted),
+                    "leakproof" => args.insert(ExternArgs::Leakproof),
+                    "window" => args.insert(ExternArgs::Window),
+                    "security_definer" => args.insert(ExternArgs::SecurityDefiner),
+                    "security_invoker" => args.insert(ExternArgs::SecurityInvoker),
                     "error" => {
                         let _punc = itr.next().unwrap();
                         let literal = itr.next().unwrap();
@@ -333,6 +409,63 @@ pub fn parse_extern_attributes(attr: TokenStream) -> HashSet<ExternArgs> {
                         let name = name[1..name.len() - 1].to_string();
                         args.insert(ExternArgs::Name(name.to_string()))
                     }
+                    "symbol" => {
+                        let _punc = itr.next().unwrap();
+                        let literal = itr.next().unwrap();
+                        let symbol = literal.to_string();
+                        let symbol = unescape::unescape(&symbol).expect("failed to unescape");
+
+                        // trim leading/trailing quotes around the literal
+                        let symbol = symbol[1..symbol.len() - 1].to_string();
+                        args.insert(ExternArgs::Symbol(symbol))
+                    }
+                    "sql" => {
+                        let _punc = itr.next().unwrap();
+                        let literal = itr.next().unwrap();
+                        let sql = literal.to_string();
+                        let sql = unescape::unescape(&sql).expect("failed to unescape");
+
+                        // trim leading/trailing quotes around the literal
+                        let sql = sql[1..sql.len() - 1].to_string();
+                        args.insert(ExternArgs::Sql(sql))
+                    }
+                    "set" => {
+                        let _punc = itr.next().unwrap();
+                        let group = itr.next().unwrap();
+                        let mut literals = match group {
+                            TokenTree::Group(g) => g.stream().into_iter(),
+                            _ => panic!("expected `set = (\"name\", \"value\")`"),
+                        }
+                        .filter_map(|t| match t {
+                            TokenTree::Literal(literal) => {
+                                let literal = literal.to_string();
+                                let literal =
+                                    unescape::unescape(&literal).expect("failed to unescape");
+
+                                // trim leading/trailing quotes around the literal
+                                Some(literal[1..literal.len() - 1].to_string())
+                            }
+                            _ => None,
+                        });
+                        let name = literals.next().expect("`set` requires a name");
+                        let value = literals.next().expect("`set` requires a value");
+                        args.insert(ExternArgs::Set(name, value))
+                    }
+                    "support" | "typmod_in" | "typmod_out" => {
+                        let _punc = itr.next().unwrap();
+                        let mut path = String::new();
+                        for t in itr.by_ref() {
+                            match &t {
+                                TokenTree::Punct(p) if p.as_char() == ',' => break,
+                                _ => path.push_str(&t.to_string()),
+                            }
+                        }
+                        args.insert(match name.as_str() {
+                            "typmod_in" => ExternArgs::TypmodIn(path),
+                            "typmod_out" => ExternArgs::TypmodOut(path),
+                            _ => ExternArgs::Support(path),
+                        })
+                    }
                     _ => false,
                 };
             }
@@ -557,7 +690,10 @@ pub fn anonymonize_lifetimes(value: &mut syn::Type) {
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_extern_attributes, ExternArgs};
+    use crate::sql_entity_graph::{Parallel, PgExtern, Volatility};
+    use crate::{parse_extern_attributes, prefix_path, ExternArgs};
+    use quote::{quote, ToTokens};
+    use std::path::PathBuf;
     use std::str::FromStr;
 
     #[test]
@@ -570,4 +706,182 @@ mod tests {
             "syntax error at or near \"THIS\"".to_string()
         )));
     }
+
+    #[test]
+    fn leakproof_requires_immutable_or_stable() {
+        let item = quote! { fn example() {} };
+
+        assert!(PgExtern::new(quote! { leakproof }, item.clone()).is_err());
+        assert!(PgExtern::new(quote! { immutable, leakproof }, item.clone()).is_ok());
+        assert!(PgExtern::new(quote! { leakproof, stable }, item).is_ok());
+    }
+
+    #[test]
+    fn window_renders_as_sql_keyword() {
+        let ts = proc_macro2::TokenStream::from_str("immutable, window").unwrap();
+
+        let args = parse_extern_attributes(ts);
+        assert!(args.contains(&ExternArgs::Window));
+        assert_eq!(ExternArgs::Window.to_string(), "WINDOW");
+    }
+
+    #[test]
+    fn parse_support() {
+        let s = "immutable, support = planner::my_support, strict";
+        let ts = proc_macro2::TokenStream::from_str(s).unwrap();
+
+        let args = parse_extern_attributes(ts);
+        assert!(args.contains(&ExternArgs::Support("planner::my_support".to_string())));
+        assert!(args.contains(&ExternArgs::Strict));
+    }
+
+    #[test]
+    fn parse_typmod() {
+        let s = "immutable, typmod_in = types::MyNumeric, typmod_out = MyNumeric, strict";
+        let ts = proc_macro2::TokenStream::from_str(s).unwrap();
+
+        let args = parse_extern_attributes(ts);
+        assert!(args.contains(&ExternArgs::TypmodIn("types::MyNumeric".to_string())));
+        assert!(args.contains(&ExternArgs::TypmodOut("MyNumeric".to_string())));
+        assert!(args.contains(&ExternArgs::Strict));
+    }
+
+    #[test]
+    fn symbol_renames_the_wrapper() {
+        let item = quote! { fn example() {} };
+
+        let default = PgExtern::new(quote! { immutable }, item.clone()).unwrap();
+        assert_eq!(default.wrapper_ident().to_string(), "example_wrapper");
+
+        let renamed =
+            PgExtern::new(quote! { name = "ex", symbol = "my_example" }, item.clone()).unwrap();
+        assert_eq!(renamed.wrapper_ident().to_string(), "my_example");
+
+        assert!(PgExtern::new(quote! { symbol = "not a symbol" }, item).is_err());
+
+        let args = parse_extern_attributes(quote! { symbol = "my_example" });
+        assert!(args.contains(&ExternArgs::Symbol("my_example".to_string())));
+    }
+
+    #[test]
+    fn query_parsed_attributes() {
+        let item = quote! { fn example() {} };
+
+        let parsed = PgExtern::new(
+            quote! { stable, strict, parallel_safe, schema = "ex" },
+            item.clone(),
+        )
+        .unwrap();
+        let attrs = parsed.extern_attrs().unwrap();
+        assert_eq!(attrs.volatility(), Some(Volatility::Stable));
+        assert_eq!(attrs.parallel(), Some(Parallel::Safe));
+        assert_eq!(attrs.schema(), Some("ex".to_string()));
+        assert!(attrs.is_strict());
+
+        let parsed = PgExtern::new(quote! { name = "ex" }, item).unwrap();
+        let attrs = parsed.extern_attrs().unwrap();
+        assert_eq!(attrs.volatility(), None);
+        assert_eq!(attrs.parallel(), None);
+        assert_eq!(attrs.schema(), None);
+        assert!(!attrs.is_strict());
+    }
+
+    #[test]
+    fn repeated_options() {
+        let item = quote! { fn example() {} };
+
+        let parsed = PgExtern::new(
+            quote! { strict, strict, name = "ex", name = "ex" },
+            item.clone(),
+        )
+        .unwrap();
+        assert_eq!(parsed.extern_attrs().unwrap().attrs.len(), 2);
+
+        let err = PgExtern::new(quote! { name = "ex", schema = "s", name = "other" }, item)
+            .err()
+            .expect("expected a repeated option error");
+        assert_eq!(
+            err.to_string(),
+            "`name` is given more than once with different values"
+        );
+    }
+
+    #[test]
+    fn parse_sql() {
+        let s = r#"sql = "CREATE FUNCTION example() RETURNS void LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';""#;
+        let ts = proc_macro2::TokenStream::from_str(s).unwrap();
+
+        let args = parse_extern_attributes(ts.clone());
+        assert!(args.contains(&ExternArgs::Sql(
+            "CREATE FUNCTION example() RETURNS void LANGUAGE c AS 'MODULE_PATHNAME', '@FUNCTION_NAME@';"
+                .to_string()
+        )));
+
+        let entity = PgExtern::new(ts, quote! { fn example() {} })
+            .unwrap()
+            .to_token_stream()
+            .to_string();
+        assert!(entity.contains("'example_wrapper';"));
+    }
+
+    #[test]
+    fn unknown_option_is_reported_by_name() {
+        let err = PgExtern::new(quote! { immutble }, quote! { fn example() {} })
+            .err()
+            .expect("expected an unknown option error");
+
+        assert!(err.to_string().starts_with("unknown option 'immutble'"));
+        assert!(err.to_string().contains("immutable"));
+    }
+
+    #[test]
+    fn security_definer_and_invoker_are_exclusive() {
+        let item = quote! { fn example() {} };
+
+        assert!(PgExtern::new(quote! { security_definer }, item.clone()).is_ok());
+        assert!(PgExtern::new(quote! { security_invoker }, item.clone()).is_ok());
+        assert!(PgExtern::new(quote! { security_definer, security_invoker }, item).is_err());
+    }
+
+    #[test]
+    fn volatility_and_parallel_options_are_exclusive() {
+        let item = quote! { fn example() {} };
+
+        assert!(PgExtern::new(quote! { immutable, parallel_safe }, item.clone()).is_ok());
+        assert!(PgExtern::new(quote! { immutable, immutable }, item.clone()).is_ok());
+        assert!(PgExtern::new(quote! { immutable, volatile }, item.clone()).is_err());
+        assert!(PgExtern::new(quote! { stable, strict, immutable }, item.clone()).is_err());
+
+        let err = PgExtern::new(quote! { parallel_safe, parallel_unsafe }, item)
+            .err()
+            .expect("expected a conflicting option error");
+        assert_eq!(
+            err.to_string(),
+            "`parallel_safe` and `parallel_unsafe` are mutually exclusive"
+        );
+    }
+
+    #[test]
+    fn prefix_path_keeps_spaces() {
+        let dir = PathBuf::from("/Applications/Postgres.app/Application Support/bin");
+        let path = prefix_path(&dir);
+
+        assert_eq!(std::env::split_paths(&path).next(), Some(dir));
+    }
+
+    #[test]
+    fn parse_set() {
+        let s = "set = (\"search_path\", \"pg_catalog\"), set = (\"work_mem\", \"'64MB'\")";
+        let ts = proc_macro2::TokenStream::from_str(s).unwrap();
+
+        let args = parse_extern_attributes(ts);
+        assert!(args.contains(&ExternArgs::Set(
+            "search_path".to_string(),
+            "pg_catalog".to_string()
+        )));
+        assert!(args.contains(&ExternArgs::Set(
+            "work_mem".to_string(),
+            "'64MB'".to_string()
+        )));
+    }
 }
@@ -24,6 +24,8 @@ impl Display for PgVersion {
 pub struct PgConfig {
     version: Option<PgVersion>,
     pg_config: Option<PathBuf>,
+    config_label: Option<String>,
+    env_var: Option<String>,
 }
 
 impl Display for PgConfig {
@@ -47,6 +49,8 @@ impl Default for PgConfig {
         PgConfig {
             version: None,
             pg_config: None,
+            config_label: None,
+            env_var: None,
         }
     }
 }
@@ -56,6 +60,8 @@ impl PgConfig {
         PgConfig {
             version: None,
             pg_config: Some(pg_config),
+            config_label: None,
+            env_var: None,
         }
     }
 
@@ -71,6 +77,45 @@ impl PgConfig {
         Ok(format!("pg{}", self.major_version()?))
     }
 
+    /// Make sure this `pg_config` can be run and, if it came from `config.toml`, that it reports
+    /// the Postgres major version it is configured as
+    pub fn validate(&self) -> Result<(), std::io::Error> {
+        let label = self.config_label.as_deref().unwrap_or("pg_config");
+        if let Some(path) = &self.pg_config {
+            // a bare `pg_config` is looked up on the $PATH instead
+            if path.components().count() > 1 && !path.exists() {
+                return Err(std::io::Error::new(
+                    ErrorKind::NotFound,
+                    format!(
+                        "`{}` is configured as `{}`, which does not exist",
+                        label.yellow(),
+                        path.display()
+                    ),
+                ));
+            }
+        }
+
+        let actual = self.label()?;
+        match &self.config_label {
+            Some(expected) if *expected != actual => Err(std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "`{}` is configured as `{}`, which reports Postgres {}",
+                    expected.yellow(),
+                    self.path().unwrap_or_default().display(),
+                    actual
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// The environment variable this `pg_config` was taken from, if it didn't come from
+    /// `config.toml`
+    pub fn env_var(&self) -> Option<&str> {
+        self.env_var.as_deref()
+    }
+
     pub fn path(&self) -> Option<PathBuf> {
         self.pg_config.clone()
     }
@@ -202,6 +247,11 @@ impl PgConfig {
         Ok(path)
     }
 
+    /// The full version string reported by `pg_config --version`, such as "PostgreSQL 14.2"
+    pub fn version_string(&self) -> Result<String, std::io::Error> {
+        self.run("--version")
+    }
+
     pub fn includedir_server(&self) -> Result<PathBuf, std::io::Error> {
         Ok(self.run("--includedir-server")?.into())
     }
@@ -280,6 +330,8 @@ impl Pgx {
                 .map(|version| PgConfig {
                     version: Some(version),
                     pg_config: None,
+                    config_label: None,
+                    env_var: None,
                 })
                 .collect(),
         })
@@ -297,10 +349,23 @@ impl Pgx {
                 // we'll get what we need from cargo-pgx' config.toml file
                 let path = Pgx::config_toml()?;
                 if !path.exists() {
+                    // without a config.toml, fall back to `PGxx_PG_CONFIG` and `PG_CONFIG`
+                    // environment variables, which are easier to provide in CI
+                    if let Some(pgx) = Pgx::from_env()? {
+                        return Ok(pgx);
+                    }
+
                     return Err(std::io::Error::new(
                         ErrorKind::NotFound,
                         format!(
-                            "{} not found.  Have you run `{}` yet?",
+                            "{} not found.  Have you run `{}` yet?\n\n\
+                            Alternatively, set `PGxx_PG_CONFIG` environment variables to the \
+                            `pg_config` of each Postgres version, such as \
+                            `PG13_PG_CONFIG=/usr/lib/postgresql/13/bin/pg_config`, set \
+                            `PG_CONFIG` to the `pg_config` of the version being built, or write \
+                            that file yourself:\n\n\
+                            [configs]\n\
+                            pg13 = \"/usr/lib/postgresql/13/bin/pg_config\"\n",
                             path.display(),
                             "cargo pgx init".bold().yellow()
                         ),
@@ -311,8 +376,10 @@ impl Pgx {
                     Ok(configs) => {
                         let mut pgx = Pgx::new();
 
-                        for (_, v) in configs.configs {
-                            pgx.push(PgConfig::new(v));
+                        for (label, v) in configs.configs {
+                            let mut pg_config = PgConfig::new(v);
+                            pg_config.config_label = Some(label);
+                            pgx.push(pg_config);
                         }
                         Ok(pgx)
                     }
@@ -322,6 +389,54 @@ impl Pgx {
         }
     }
 
+    /// Build the set of Postgres installations from environment variables named like
+    /// `PG13_PG_CONFIG`, each holding the path to that version's `pg_config`.  `PG_CONFIG` is also
+    /// used for whichever version it reports, unless that version has a `PGxx_PG_CONFIG` of its own
+    fn from_env() -> Result<Option<Self>, std::io::Error> {
+        let mut pg_configs = Vec::new();
+        for (key, value) in std::env::vars_os() {
+            let key = match key.to_str() {
+                Some(key) => key,
+                None => continue,
+            };
+            let major_version = match key
+                .strip_prefix("PG")
+                .and_then(|key| key.strip_suffix("_PG_CONFIG"))
+                .and_then(|major| major.parse::<u16>().ok())
+            {
+                Some(major_version) => major_version,
+                None => continue,
+            };
+
+            let mut pg_config = PgConfig::new(value.into());
+            pg_config.config_label = Some(format!("pg{}", major_version));
+            pg_config.env_var = Some(key.to_string());
+            pg_configs.push((major_version, pg_config));
+        }
+
+        if let Some(path) = std::env::var_os("PG_CONFIG") {
+            let mut pg_config = PgConfig::new(path.into());
+            let major_version = pg_config.major_version()?;
+            if !pg_configs.iter().any(|(major, _)| *major == major_version) {
+                pg_config.env_var = Some("PG_CONFIG".to_string());
+                pg_configs.push((major_version, pg_config));
+            }
+        }
+
+        if pg_configs.is_empty() {
+            return Ok(None);
+        }
+
+        // the environment is in no particular order
+        pg_configs.sort_by_key(|(major_version, _)| *major_version);
+        Ok(Some(Pgx {
+            pg_configs: pg_configs
+                .into_iter()
+                .map(|(_, pg_config)| pg_config)
+                .collect(),
+        }))
+    }
+
     pub fn push(&mut self, pg_config: PgConfig) {
         self.pg_configs.push(pg_config);
     }
@@ -332,12 +447,10 @@ impl Pgx {
     ) -> impl std::iter::Iterator<Item = Result<&PgConfig, std::io::Error>> {
         match which {
             PgConfigSelector::All => {
+                // a config whose version can't be determined is left for `PgConfig::validate()` to
+                // report, rather than panicking here
                 let mut configs = self.pg_configs.iter().collect::<Vec<_>>();
-                configs.sort_by(|a, b| {
-                    a.major_version()
-                        .expect("no major version")
-                        .cmp(&b.major_version().expect("no major version"))
-                });
+                configs.sort_by_cached_key(|c| c.major_version().ok());
 
                 configs
                     .into_iter()
@@ -350,8 +463,18 @@ impl Pgx {
     }
 
     pub fn get(&self, label: &str) -> Result<&PgConfig, std::io::Error> {
+        // configs from `config.toml` or `PGxx_PG_CONFIG` are known by their key, so there's no need
+        // to run (possibly stale) `pg_config` binaries of other versions to find the right one
+        if let Some(pg_config) = self
+            .pg_configs
+            .iter()
+            .find(|pg_config| pg_config.config_label.as_deref() == Some(label))
+        {
+            return Ok(pg_config);
+        }
+
         for pg_config in self.pg_configs.iter() {
-            if pg_config.label()? == label {
+            if pg_config.config_label.is_none() && pg_config.label()? == label {
                 return Ok(pg_config);
             }
         }
@@ -9,7 +9,7 @@ mod postgres_type;
 
 pub use super::ExternArgs;
 pub use extension_sql::{ExtensionSql, ExtensionSqlFile, SqlDeclared};
-pub use pg_extern::{Argument, PgExtern, PgOperator};
+pub use pg_extern::{Argument, Parallel, PgExtern, PgOperator, PgxAttributes, Volatility};
 pub use pg_schema::Schema;
 pub use positioning_ref::PositioningRef;
 pub use postgres_enum::PostgresEnum;
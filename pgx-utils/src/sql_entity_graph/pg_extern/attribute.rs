@@ -1,5 +1,5 @@
 use crate::sql_entity_graph::PositioningRef;
-use proc_macro2::{Span, TokenStream as TokenStream2};
+use proc_macro2::TokenStream as TokenStream2;
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::{
     parse::{Parse, ParseStream},
@@ -14,10 +14,153 @@ pub struct PgxAttributes {
 
 impl Parse for PgxAttributes {
     fn parse(input: ParseStream) -> Result<Self, syn::Error> {
-        Ok(Self {
-            attrs: input.parse_terminated(Attribute::parse)?,
+        let mut attrs = Punctuated::<Attribute, Token![,]>::new();
+        let mut spans = Vec::new();
+        while !input.is_empty() {
+            let span = input.span();
+            let attr: Attribute = input.parse()?;
+
+            // an exact repeat adds nothing, but two different values for an option that only takes
+            // one would leave it unclear which was meant
+            if !attrs.iter().any(|existing| *existing == attr) {
+                if let Some(name) = attr.single_valued_name() {
+                    if attrs
+                        .iter()
+                        .any(|existing| existing.single_valued_name() == Some(name))
+                    {
+                        return Err(syn::Error::new(
+                            span,
+                            format!("`{}` is given more than once with different values", name),
+                        ));
+                    }
+                }
+                attrs.push(attr);
+                spans.push(span);
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        // `LEAKPROOF` is only meaningful on functions Postgres can treat as side-effect free
+        let is_immutable_or_stable = attrs
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Immutable | Attribute::Stable));
+        if !is_immutable_or_stable {
+            if let Some((_, span)) = attrs
+                .iter()
+                .zip(spans.iter())
+                .find(|(attr, _)| **attr == Attribute::Leakproof)
+            {
+                return Err(syn::Error::new(
+                    *span,
+                    "`leakproof` requires the function to also be `immutable` or `stable`",
+                ));
+            }
+        }
+
+        // only one option from each of these groups may be given
+        let exclusive_groups: &[&[(Attribute, &str)]] = &[
+            &[
+                (Attribute::Immutable, "immutable"),
+                (Attribute::Stable, "stable"),
+                (Attribute::Volatile, "volatile"),
+            ],
+            &[
+                (Attribute::ParallelSafe, "parallel_safe"),
+                (Attribute::ParallelUnsafe, "parallel_unsafe"),
+                (Attribute::ParallelRestricted, "parallel_restricted"),
+            ],
+            &[
+                (Attribute::SecurityDefiner, "security_definer"),
+                (Attribute::SecurityInvoker, "security_invoker"),
+            ],
+        ];
+        for group in exclusive_groups {
+            let mut seen: Option<&(Attribute, &str)> = None;
+            for (attr, span) in attrs.iter().zip(spans.iter()) {
+                let found = match group.iter().find(|(option, _)| option == attr) {
+                    Some(found) => found,
+                    None => continue,
+                };
+                match seen {
+                    Some(first) if first.0 != found.0 => {
+                        return Err(syn::Error::new(
+                            *span,
+                            format!("`{}` and `{}` are mutually exclusive", first.1, found.1),
+                        ))
+                    }
+                    _ => seen = Some(found),
+                }
+            }
+        }
+
+        Ok(Self { attrs })
+    }
+}
+
+impl PgxAttributes {
+    /// The function's volatility, if one was given
+    pub fn volatility(&self) -> Option<Volatility> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attribute::Immutable => Some(Volatility::Immutable),
+            Attribute::Stable => Some(Volatility::Stable),
+            Attribute::Volatile => Some(Volatility::Volatile),
+            _ => None,
+        })
+    }
+
+    /// The function's parallel safety, if one was given
+    pub fn parallel(&self) -> Option<Parallel> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attribute::ParallelSafe => Some(Parallel::Safe),
+            Attribute::ParallelUnsafe => Some(Parallel::Unsafe),
+            Attribute::ParallelRestricted => Some(Parallel::Restricted),
+            _ => None,
         })
     }
+
+    /// The value of `schema = "..."`, if given
+    pub fn schema(&self) -> Option<String> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attribute::Schema(schema) => Some(schema.value()),
+            _ => None,
+        })
+    }
+
+    /// The value of `name = "..."`, if given
+    pub fn name(&self) -> Option<String> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attribute::Name(name) => Some(name.value()),
+            _ => None,
+        })
+    }
+
+    /// The value of `symbol = "..."`, if given
+    pub fn symbol(&self) -> Option<String> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attribute::Symbol(symbol) => Some(symbol.value()),
+            _ => None,
+        })
+    }
+
+    /// The value of `sql = "..."`, if given
+    pub fn sql(&self) -> Option<String> {
+        self.attrs.iter().find_map(|attr| match attr {
+            Attribute::Sql(sql) => Some(sql.value()),
+            _ => None,
+        })
+    }
+
+    /// Was `strict` given?
+    ///
+    /// Note that `#[pg_extern]` also makes functions without `Option` arguments `STRICT`, which
+    /// this doesn't account for
+    pub fn is_strict(&self) -> bool {
+        self.attrs.iter().any(|attr| *attr == Attribute::Strict)
+    }
 }
 
 impl ToTokens for PgxAttributes {
@@ -30,6 +173,22 @@ impl ToTokens for PgxAttributes {
     }
 }
 
+/// The volatility categories `#[pg_extern]` accepts, see [`PgxAttributes::volatility`]
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Volatility {
+    Immutable,
+    Stable,
+    Volatile,
+}
+
+/// The parallel safety categories `#[pg_extern]` accepts, see [`PgxAttributes::parallel`]
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Parallel {
+    Safe,
+    Unsafe,
+    Restricted,
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum Attribute {
     Immutable,
@@ -41,12 +200,42 @@ pub enum Attribute {
     ParallelSafe,
     ParallelUnsafe,
     ParallelRestricted,
+    Leakproof,
+    Window,
+    SecurityDefiner,
+    SecurityInvoker,
     Error(syn::LitStr),
     Schema(syn::LitStr),
     Name(syn::LitStr),
+    Symbol(syn::LitStr),
+    Sql(syn::LitStr),
+    Set {
+        name: syn::LitStr,
+        value: syn::LitStr,
+    },
+    Support(syn::Path),
+    TypmodIn(syn::Path),
+    TypmodOut(syn::Path),
     Requires(Punctuated<PositioningRef, Token![,]>),
 }
 
+impl Attribute {
+    /// The name of an option that may only be given one value, such as `name = "..."`
+    fn single_valued_name(&self) -> Option<&'static str> {
+        match self {
+            Attribute::Error(_) => Some("error"),
+            Attribute::Schema(_) => Some("schema"),
+            Attribute::Name(_) => Some("name"),
+            Attribute::Symbol(_) => Some("symbol"),
+            Attribute::Sql(_) => Some("sql"),
+            Attribute::Support(_) => Some("support"),
+            Attribute::TypmodIn(_) => Some("typmod_in"),
+            Attribute::TypmodOut(_) => Some("typmod_out"),
+            _ => None,
+        }
+    }
+}
+
 impl ToTokens for Attribute {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let quoted = match self {
@@ -65,6 +254,16 @@ impl ToTokens for Attribute {
             Attribute::ParallelRestricted => {
                 quote! { pgx::datum::sql_entity_graph::ExternArgs::ParallelRestricted }
             }
+            Attribute::Leakproof => {
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::Leakproof }
+            }
+            Attribute::Window => quote! { pgx::datum::sql_entity_graph::ExternArgs::Window },
+            Attribute::SecurityDefiner => {
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::SecurityDefiner }
+            }
+            Attribute::SecurityInvoker => {
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::SecurityInvoker }
+            }
             Attribute::Error(s) => {
                 quote! { pgx::datum::sql_entity_graph::ExternArgs::Error(String::from(#s)) }
             }
@@ -74,6 +273,27 @@ impl ToTokens for Attribute {
             Attribute::Name(s) => {
                 quote! { pgx::datum::sql_entity_graph::ExternArgs::Name(String::from(#s)) }
             }
+            Attribute::Symbol(s) => {
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::Symbol(String::from(#s)) }
+            }
+            Attribute::Sql(s) => {
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::Sql(String::from(#s)) }
+            }
+            Attribute::Set { name, value } => {
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::Set(String::from(#name), String::from(#value)) }
+            }
+            Attribute::Support(path) => {
+                let path = path.to_token_stream().to_string().replace(" ", "");
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::Support(String::from(#path)) }
+            }
+            Attribute::TypmodIn(ty) => {
+                let ty = ty.to_token_stream().to_string().replace(" ", "");
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::TypmodIn(String::from(#ty)) }
+            }
+            Attribute::TypmodOut(ty) => {
+                let ty = ty.to_token_stream().to_string().replace(" ", "");
+                quote! { pgx::datum::sql_entity_graph::ExternArgs::TypmodOut(String::from(#ty)) }
+            }
             Attribute::Requires(items) => {
                 let items_iter = items
                     .iter()
@@ -86,6 +306,35 @@ impl ToTokens for Attribute {
     }
 }
 
+impl Attribute {
+    /// The option names accepted by `#[pg_extern(...)]`, used when reporting unknown options.
+    const VALID_OPTIONS: &'static [&'static str] = &[
+        "immutable",
+        "strict",
+        "stable",
+        "volatile",
+        "raw",
+        "no_guard",
+        "parallel_safe",
+        "parallel_unsafe",
+        "parallel_restricted",
+        "leakproof",
+        "window",
+        "security_definer",
+        "security_invoker",
+        "error",
+        "schema",
+        "name",
+        "symbol",
+        "sql",
+        "set",
+        "support",
+        "typmod_in",
+        "typmod_out",
+        "requires",
+    ];
+}
+
 impl Parse for Attribute {
     fn parse(input: ParseStream) -> Result<Self, syn::Error> {
         let ident: syn::Ident = input.parse()?;
@@ -99,6 +348,10 @@ impl Parse for Attribute {
             "parallel_safe" => Self::ParallelSafe,
             "parallel_unsafe" => Self::ParallelUnsafe,
             "parallel_restricted" => Self::ParallelRestricted,
+            "leakproof" => Self::Leakproof,
+            "window" => Self::Window,
+            "security_definer" => Self::SecurityDefiner,
+            "security_invoker" => Self::SecurityInvoker,
             "error" => {
                 let _eq: Token![=] = input.parse()?;
                 let literal: syn::LitStr = input.parse()?;
@@ -114,13 +367,63 @@ impl Parse for Attribute {
                 let literal: syn::LitStr = input.parse()?;
                 Self::Name(literal)
             }
+            "symbol" => {
+                let _eq: Token![=] = input.parse()?;
+                let literal: syn::LitStr = input.parse()?;
+                // the symbol becomes the name of the generated wrapper function
+                if syn::parse_str::<syn::Ident>(&literal.value()).is_err() {
+                    return Err(syn::Error::new(
+                        literal.span(),
+                        "`symbol` must be a valid Rust and C identifier",
+                    ));
+                }
+                Self::Symbol(literal)
+            }
+            "sql" => {
+                let _eq: Token![=] = input.parse()?;
+                let literal: syn::LitStr = input.parse()?;
+                Self::Sql(literal)
+            }
+            "set" => {
+                let _eq: Token![=] = input.parse()?;
+                let content;
+                let _paren = syn::parenthesized!(content in input);
+                let name: syn::LitStr = content.parse()?;
+                let _comma: Token![,] = content.parse()?;
+                let value: syn::LitStr = content.parse()?;
+                Self::Set { name, value }
+            }
+            "support" => {
+                let _eq: Token![=] = input.parse()?;
+                let path: syn::Path = input.parse()?;
+                Self::Support(path)
+            }
+            "typmod_in" => {
+                let _eq: Token![=] = input.parse()?;
+                let ty: syn::Path = input.parse()?;
+                Self::TypmodIn(ty)
+            }
+            "typmod_out" => {
+                let _eq: Token![=] = input.parse()?;
+                let ty: syn::Path = input.parse()?;
+                Self::TypmodOut(ty)
+            }
             "requires" => {
                 let _eq: syn::token::Eq = input.parse()?;
                 let content;
                 let _bracket = syn::bracketed!(content in input);
                 Self::Requires(content.parse_terminated(PositioningRef::parse)?)
             }
-            _ => return Err(syn::Error::new(Span::call_site(), "Invalid option")),
+            unknown => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "unknown option '{}'; expected one of {}",
+                        unknown,
+                        Self::VALID_OPTIONS.join(", ")
+                    ),
+                ))
+            }
         };
         Ok(found)
     }
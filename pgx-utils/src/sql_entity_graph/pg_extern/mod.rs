@@ -5,7 +5,7 @@ mod returning;
 mod search_path;
 
 pub use argument::Argument;
-use attribute::{Attribute, PgxAttributes};
+pub use attribute::{Parallel, PgxAttributes, Volatility};
 pub use operator::PgOperator;
 use operator::{PgxOperatorAttributeWithIdent, PgxOperatorOpName};
 use returning::Returning;
@@ -50,25 +50,27 @@ impl PgExtern {
     fn name(&self) -> String {
         self.attrs
             .as_ref()
-            .and_then(|a| {
-                a.attrs.iter().find_map(|candidate| match candidate {
-                    Attribute::Name(name) => Some(name.value()),
-                    _ => None,
-                })
-            })
+            .and_then(PgxAttributes::name)
             .unwrap_or_else(|| self.func.sig.ident.to_string())
     }
 
+    /// The exported symbol Postgres calls, which is `{ident}_wrapper` unless overridden with
+    /// `symbol = "..."`
+    pub fn wrapper_ident(&self) -> Ident {
+        let symbol = self
+            .attrs
+            .as_ref()
+            .and_then(PgxAttributes::symbol)
+            .unwrap_or_else(|| format!("{}_wrapper", self.func.sig.ident));
+        Ident::new(&symbol, self.func.sig.ident.span())
+    }
+
     fn schema(&self) -> Option<String> {
-        self.attrs.as_ref().and_then(|a| {
-            a.attrs.iter().find_map(|candidate| match candidate {
-                Attribute::Schema(name) => Some(name.value()),
-                _ => None,
-            })
-        })
+        self.attrs.as_ref().and_then(PgxAttributes::schema)
     }
 
-    fn extern_attrs(&self) -> Option<&PgxAttributes> {
+    /// The parsed `#[pg_extern(...)]` options, if any
+    pub fn extern_attrs(&self) -> Option<&PgxAttributes> {
         self.attrs.as_ref()
     }
 
@@ -77,6 +79,12 @@ impl PgExtern {
     }
 
     fn overridden(&self) -> Option<String> {
+        // an explicit `sql = "..."` takes precedence over a `pgxsql` doc comment block
+        let sql_attr = self.attrs.as_ref().and_then(PgxAttributes::sql);
+        if let Some(sql) = sql_attr {
+            return Some(sql.replace("@FUNCTION_NAME@", &self.wrapper_ident().to_string()));
+        }
+
         let mut retval = None;
         let mut in_commented_sql_block = false;
         for attr in &self.func.attrs {
@@ -94,10 +102,11 @@ impl PgExtern {
                             in_commented_sql_block = false;
                         } else if in_commented_sql_block {
                             let sql = retval.get_or_insert_with(String::default);
-                            let line = inner.value().trim_start().replace(
-                                "@FUNCTION_NAME@",
-                                &*(self.func.sig.ident.to_string() + "_wrapper"),
-                            ) + "\n";
+                            let line = inner
+                                .value()
+                                .trim_start()
+                                .replace("@FUNCTION_NAME@", &self.wrapper_ident().to_string())
+                                + "\n";
                             sql.push_str(&*line);
                         }
                     }
@@ -190,7 +199,7 @@ impl PgExtern {
     }
 
     pub fn new(attr: TokenStream2, item: TokenStream2) -> Result<Self, syn::Error> {
-        let attrs = syn::parse2::<PgxAttributes>(attr.clone()).ok();
+        let attrs = Some(syn::parse2::<PgxAttributes>(attr.clone())?);
         let func = syn::parse2::<syn::ItemFn>(item)?;
         Ok(Self {
             attrs: attrs,
@@ -10,7 +10,7 @@ pub use returning::PgExternReturnEntity;
 
 use pgx_utils::ExternArgs;
 
-use super::{SqlGraphEntity, SqlGraphIdentifier, ToSql};
+use super::{pgx_sql::find_extern_target, SqlGraphEntity, SqlGraphIdentifier, ToSql};
 use pgx_utils::sql_entity_graph::SqlDeclared;
 use std::cmp::Ordering;
 
@@ -94,16 +94,51 @@ impl ToSql for PgExternEntity {
             extern_attrs.push(ExternArgs::Strict);
         }
 
+        let support = self
+            .extern_attrs
+            .iter()
+            .find_map(|x| match x {
+                ExternArgs::Support(path) => Some(path),
+                _ => None,
+            })
+            .map(|path| {
+                find_extern_target(path, &context.externs)
+                    .map(|(other, other_index)| {
+                        format!(
+                            "SUPPORT {schema}\"{name}\"\n",
+                            schema = other
+                                .schema
+                                .map(|schema| format!("{}.", schema))
+                                .unwrap_or_else(|| context.schema_prefix_for(other_index)),
+                            name = other.name,
+                        )
+                    })
+                    .ok_or_else(|| {
+                        eyre_err!(
+                            "Could not find `support` function `{}` for function `{}`.",
+                            path,
+                            self.name
+                        )
+                    })
+            })
+            .transpose()?
+            .unwrap_or_default();
+
         let fn_sql = format!("\
                                 CREATE OR REPLACE FUNCTION {schema}\"{name}\"({arguments}) {returns}\n\
                                 {extern_attrs}\
                                 {search_path}\
+                                {set}\
+                                {support}\
                                 LANGUAGE c /* Rust */\n\
-                                AS 'MODULE_PATHNAME', '{unaliased_name}_wrapper';\
+                                AS 'MODULE_PATHNAME', '{symbol}';\
                             ",
                              schema = self.schema.map(|schema| format!("{}.", schema)).unwrap_or_else(|| context.schema_prefix_for(&self_index)),
                              name = self.name,
-                             unaliased_name = self.unaliased_name,
+                             symbol = self.extern_attrs.iter().find_map(|attr| match attr {
+                                 ExternArgs::Symbol(symbol) => Some(symbol.clone()),
+                                 _ => None,
+                             }).unwrap_or_else(|| format!("{}_wrapper", self.unaliased_name)),
                              arguments = if !self.fn_args.is_empty() {
                                  let mut args = Vec::new();
                                  for (idx, arg) in self.fn_args.iter().enumerate() {
@@ -233,6 +268,11 @@ impl ToSql for PgExternEntity {
                                  },
                                  PgExternReturnEntity::Trigger => String::from("RETURNS trigger"),
                              },
+                             set = self.extern_attrs.iter().filter_map(|attr| match attr {
+                                 ExternArgs::Set(name, value) => Some(format!("SET {} = {}\n", name, value)),
+                                 _ => None,
+                             }).collect::<String>(),
+                             support = support,
                              search_path = if let Some(search_path) = &self.search_path {
                                  let retval = format!("SET search_path TO {}", search_path.join(", "));
                                  retval + "\n"
@@ -264,9 +304,13 @@ impl ToSql for PgExternEntity {
                     .map(|f| format!("-- {}", f))
                     .collect::<Vec<_>>()
                     .join("\n");
-                inner.push_str(
-                    "\n--\n-- Overridden as (due to a `///` comment with a `pgxsql` code block):",
-                );
+                if self.extern_attrs.iter().any(|attr| matches!(attr, ExternArgs::Sql(_))) {
+                    inner.push_str("\n--\n-- Overridden as (due to a `sql` attribute):");
+                } else {
+                    inner.push_str(
+                        "\n--\n-- Overridden as (due to a `///` comment with a `pgxsql` code block):",
+                    );
+                }
                 inner
             } else {
                 fn_sql
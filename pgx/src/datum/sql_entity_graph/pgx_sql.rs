@@ -509,6 +509,24 @@ pub fn find_positioning_ref_target<'a>(
     None
 }
 
+/// Find the `#[pg_extern]` function a Rust path refers to, such as a `support` function.
+///
+/// Unlike [`find_positioning_ref_target`] this only considers functions, so a type or enum that
+/// happens to share the function's name can't be picked instead.
+pub fn find_extern_target<'a>(
+    path: &str,
+    externs: &'a HashMap<PgExternEntity, NodeIndex>,
+) -> Option<(&'a PgExternEntity, &'a NodeIndex)> {
+    // The best we can do here is a fuzzy search.
+    let segments = path.split("::").collect::<Vec<_>>();
+    let last_segment = segments.last().expect("Expected at least one segment.");
+    let module_path = segments[..segments.len() - 1].join("::");
+
+    externs.iter().find(|(other, _)| {
+        *last_segment == other.unaliased_name && other.module_path.ends_with(&module_path)
+    })
+}
+
 fn connect_extension_sqls(
     graph: &mut StableGraph<SqlGraphEntity, SqlGraphRelationship>,
     extension_sqls: &HashMap<ExtensionSqlEntity, NodeIndex>,
@@ -792,6 +810,36 @@ fn connect_externs(
                         }
                     }
                 }
+                pgx_utils::ExternArgs::Support(support) => {
+                    if let Some((_, target)) = find_extern_target(support, externs) {
+                        tracing::debug!(from = %item.rust_identifier(), to = %graph[*target].rust_identifier(), "Adding Extern after support function edge");
+                        graph.add_edge(*target, index, SqlGraphRelationship::RequiredBy);
+                    } else {
+                        return Err(eyre_err!("Could not find `support` function: {:?}", support));
+                    }
+                }
+                pgx_utils::ExternArgs::TypmodIn(ty) | pgx_utils::ExternArgs::TypmodOut(ty) => {
+                    let ty = PositioningRef::FullPath(ty.clone());
+                    match find_positioning_ref_target(
+                        &ty,
+                        types,
+                        enums,
+                        externs,
+                        schemas,
+                        extension_sqls,
+                    ) {
+                        Some(target) if matches!(graph[*target], SqlGraphEntity::Type(_)) => {
+                            tracing::debug!(from = %item.rust_identifier(), to = %graph[*target].rust_identifier(), "Adding Type after typmod function edge");
+                            graph.add_edge(index, *target, SqlGraphRelationship::RequiredBy);
+                        }
+                        _ => {
+                            return Err(eyre_err!(
+                                "Could not find `typmod_in`/`typmod_out` type: {:?}",
+                                ty
+                            ))
+                        }
+                    }
+                }
                 _ => (),
             }
         }
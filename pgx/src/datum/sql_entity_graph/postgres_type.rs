@@ -1,5 +1,6 @@
-use super::RustSqlMapping;
+use super::{pgx_sql::find_positioning_ref_target, PositioningRef, RustSqlMapping};
 use eyre::eyre as eyre_err;
+use pgx_utils::ExternArgs;
 use std::{
     cmp::Ordering,
     hash::{Hash, Hasher},
@@ -160,6 +161,42 @@ impl ToSql for PostgresTypeEntity {
         let out_fn_sql = out_fn.to_sql(context)?;
         tracing::trace!(%out_fn_sql);
 
+        // Functions marked `typmod_in`/`typmod_out` for this type were ordered before it in the graph
+        let mut typmod_fns = context
+            .externs
+            .iter()
+            .flat_map(|(func, func_index)| {
+                func.extern_attrs.iter().filter_map(move |attr| match attr {
+                    ExternArgs::TypmodIn(ty) => Some(("TYPMOD_IN", ty, func, func_index)),
+                    ExternArgs::TypmodOut(ty) => Some(("TYPMOD_OUT", ty, func, func_index)),
+                    _ => None,
+                })
+            })
+            .filter(|(_, ty, _, _)| {
+                find_positioning_ref_target(
+                    &PositioningRef::FullPath(ty.to_string()),
+                    &context.types,
+                    &context.enums,
+                    &context.externs,
+                    &context.schemas,
+                    &context.extension_sqls,
+                ) == Some(&self_index)
+            })
+            .map(|(option, _, func, func_index)| {
+                format!(
+                    "\t{option} = {schema}\"{name}\", /* {full_path} */\n",
+                    option = option,
+                    schema = func
+                        .schema
+                        .map(|schema| format!("{}.", schema))
+                        .unwrap_or_else(|| context.schema_prefix_for(func_index)),
+                    name = func.name,
+                    full_path = func.full_path,
+                )
+            })
+            .collect::<Vec<_>>();
+        typmod_fns.sort();
+
         let shell_type = format!(
             "\n\
                                 -- {file}:{line}\n\
@@ -181,6 +218,7 @@ impl ToSql for PostgresTypeEntity {
                                     \tINTERNALLENGTH = variable,\n\
                                     \tINPUT = {schema_prefix_in_fn}{in_fn}, /* {in_fn_path} */\n\
                                     \tOUTPUT = {schema_prefix_out_fn}{out_fn}, /* {out_fn_path} */\n\
+                                    {typmod_fns}\
                                     \tSTORAGE = extended\n\
                                 );\
                             ",
@@ -195,6 +233,7 @@ impl ToSql for PostgresTypeEntity {
                                         schema_prefix_out_fn = context.schema_prefix_for(&out_fn_graph_index),
                                         out_fn = item.out_fn,
                                         out_fn_path = out_fn_path,
+                                        typmod_fns = typmod_fns.join(""),
         );
         tracing::debug!(sql = %materialized_type);
 